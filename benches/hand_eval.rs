@@ -0,0 +1,33 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+#[path = "../src/cards.rs"]
+mod cards;
+#[path = "../src/bitset.rs"]
+mod bitset;
+#[path = "../src/hands.rs"]
+mod hands;
+
+use cards::Card;
+use hands::Hand;
+use itertools::Itertools;
+
+fn seven_cards() -> Vec<Card> {
+    Card::iter().take(7).collect_vec()
+}
+
+fn bench_best_hand(c: &mut Criterion) {
+    let cards = seven_cards();
+    c.bench_function("best_hand (direct mask classification + card selection)", |b| {
+        b.iter(|| Hand::best_hand(black_box(&cards)))
+    });
+}
+
+fn bench_evaluate7(c: &mut Criterion) {
+    let mask = bitset::mask_for(&seven_cards());
+    c.bench_function("evaluate7 (score only, no card selection)", |b| {
+        b.iter(|| bitset::evaluate7(black_box(mask)))
+    });
+}
+
+criterion_group!(benches, bench_best_hand, bench_evaluate7);
+criterion_main!(benches);
@@ -0,0 +1,341 @@
+use crate::cards::Card;
+
+// `HandType` classes in strength order, used as the top bits of an
+// `evaluate7` score.
+const HIGH_CARD: u32 = 0;
+const PAIR: u32 = 1;
+const TWO_PAIR: u32 = 2;
+const THREE_OF_A_KIND: u32 = 3;
+const STRAIGHT: u32 = 4;
+const FLUSH: u32 = 5;
+const FULL_HOUSE: u32 = 6;
+const FOUR_OF_A_KIND: u32 = 7;
+const STRAIGHT_FLUSH: u32 = 8;
+
+// A-2-3-4-5, the lowest straight: bits for Two, Three, Four, Five, Ace.
+const WHEEL: u16 = 0b1_0000_0000_1111;
+
+// The best 5-card hand within a `mask`'s cards, classified once so both
+// `evaluate7` (a comparable score) and `best_five` (the concrete cards)
+// can be derived from it without re-deriving the logic twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Classification {
+    HighCard([u8; 5]),
+    Pair { pair: u8, kickers: [u8; 3] },
+    TwoPair { high: u8, low: u8, kicker: u8 },
+    ThreeOfAKind { trips: u8, kickers: [u8; 2] },
+    Straight(u8),
+    Flush { suit: u8, ranks: [u8; 5] },
+    FullHouse { trips: u8, pair: u8 },
+    FourOfAKind { quad: u8, kicker: u8 },
+    StraightFlush { suit: u8, top: u8 },
+}
+
+pub fn mask_for(cards: &[Card]) -> u64 {
+    cards
+        .iter()
+        .fold(0u64, |mask, card| mask | (1 << card.bit_index()))
+}
+
+// Totally-ordered score for the best hand within `mask` (up to 7 cards,
+// each bit `rank * 4 + suit`): `(hand_type_class << 20) | kickers`.
+pub fn evaluate7(mask: u64) -> u32 {
+    score(&classify(mask))
+}
+
+// The concrete 5 cards making up the best hand within `mask`, derived
+// directly from the mask (no `C(n,5)` enumeration).
+pub fn best_five(mask: u64) -> [Card; 5] {
+    select_cards(mask, &classify(mask))
+}
+
+fn classify(mask: u64) -> Classification {
+    let lanes = [
+        suit_lane(mask, 0),
+        suit_lane(mask, 1),
+        suit_lane(mask, 2),
+        suit_lane(mask, 3),
+    ];
+    let rank_mask = lanes[0] | lanes[1] | lanes[2] | lanes[3];
+
+    if let Some((suit, lane)) = flush_lane(&lanes) {
+        if let Some(top) = straight_top(lane) {
+            return Classification::StraightFlush { suit, top };
+        }
+    }
+
+    let mut counts = [0u32; 13];
+    for (rank, count) in counts.iter_mut().enumerate() {
+        *count = ((mask >> (rank * 4)) & 0b1111).count_ones();
+    }
+
+    let mut groups: Vec<(u32, u8)> = (0..13u8)
+        .filter(|&rank| counts[rank as usize] > 0)
+        .map(|rank| (counts[rank as usize], rank))
+        .collect();
+    // Descending by count, then by rank, so ties break toward the higher card.
+    groups.sort_by(|a, b| b.cmp(a));
+
+    if groups[0].0 == 4 {
+        let quad = groups[0].1;
+        let kicker = remaining_ranks(&groups, &[quad])[0];
+        return Classification::FourOfAKind { quad, kicker };
+    }
+
+    if groups[0].0 == 3 {
+        if let Some(&(_, pair)) = groups.iter().find(|&&(count, rank)| count >= 2 && rank != groups[0].1) {
+            return Classification::FullHouse {
+                trips: groups[0].1,
+                pair,
+            };
+        }
+    }
+
+    if let Some((suit, lane)) = flush_lane(&lanes) {
+        return Classification::Flush {
+            suit,
+            ranks: top5(lane),
+        };
+    }
+
+    if let Some(top) = straight_top(rank_mask) {
+        return Classification::Straight(top);
+    }
+
+    if groups[0].0 == 3 {
+        let trips = groups[0].1;
+        let kickers = remaining_ranks(&groups, &[trips]);
+        return Classification::ThreeOfAKind {
+            trips,
+            kickers: [kickers[0], kickers[1]],
+        };
+    }
+
+    if groups[0].0 == 2 && groups.get(1).is_some_and(|&(count, _)| count == 2) {
+        let (high, low) = (groups[0].1, groups[1].1);
+        let kicker = remaining_ranks(&groups, &[high, low])[0];
+        return Classification::TwoPair { high, low, kicker };
+    }
+
+    if groups[0].0 == 2 {
+        let pair = groups[0].1;
+        let kickers = remaining_ranks(&groups, &[pair]);
+        return Classification::Pair {
+            pair,
+            kickers: [kickers[0], kickers[1], kickers[2]],
+        };
+    }
+
+    Classification::HighCard(top5(rank_mask))
+}
+
+fn score(classification: &Classification) -> u32 {
+    match *classification {
+        Classification::HighCard(ranks) => pack(HIGH_CARD, pack_kickers(&ranks)),
+        Classification::Pair { pair, kickers } => {
+            pack(PAIR, (pair as u32) << 12 | pack_kickers(&kickers))
+        }
+        Classification::TwoPair { high, low, kicker } => pack(
+            TWO_PAIR,
+            (high as u32) << 8 | (low as u32) << 4 | kicker as u32,
+        ),
+        Classification::ThreeOfAKind { trips, kickers } => {
+            pack(THREE_OF_A_KIND, (trips as u32) << 8 | pack_kickers(&kickers))
+        }
+        Classification::Straight(top) => pack(STRAIGHT, top as u32),
+        Classification::Flush { ranks, .. } => pack(FLUSH, pack_kickers(&ranks)),
+        Classification::FullHouse { trips, pair } => {
+            pack(FULL_HOUSE, (trips as u32) << 4 | pair as u32)
+        }
+        Classification::FourOfAKind { quad, kicker } => {
+            pack(FOUR_OF_A_KIND, (quad as u32) << 4 | kicker as u32)
+        }
+        Classification::StraightFlush { top, .. } => pack(STRAIGHT_FLUSH, top as u32),
+    }
+}
+
+// Picks the concrete `Card`s matching a classification. Bit index
+// `rank * 4 + suit` uniquely identifies a card, so it can be
+// reconstructed directly from a set bit without the original `Vec<Card>`.
+fn select_cards(mask: u64, classification: &Classification) -> [Card; 5] {
+    let cards: Vec<Card> = match *classification {
+        Classification::StraightFlush { suit, top } => straight_ranks(top)
+            .iter()
+            .map(|&rank| card_at(mask, rank, Some(suit)))
+            .collect(),
+        Classification::FourOfAKind { quad, kicker } => cards_of_rank(mask, quad, 4)
+            .into_iter()
+            .chain([card_at(mask, kicker, None)])
+            .collect(),
+        Classification::FullHouse { trips, pair } => cards_of_rank(mask, trips, 3)
+            .into_iter()
+            .chain(cards_of_rank(mask, pair, 2))
+            .collect(),
+        Classification::Flush { suit, ranks } => ranks
+            .iter()
+            .map(|&rank| card_at(mask, rank, Some(suit)))
+            .collect(),
+        Classification::Straight(top) => straight_ranks(top)
+            .iter()
+            .map(|&rank| card_at(mask, rank, None))
+            .collect(),
+        Classification::ThreeOfAKind { trips, kickers } => cards_of_rank(mask, trips, 3)
+            .into_iter()
+            .chain(kickers.iter().map(|&rank| card_at(mask, rank, None)))
+            .collect(),
+        Classification::TwoPair { high, low, kicker } => cards_of_rank(mask, high, 2)
+            .into_iter()
+            .chain(cards_of_rank(mask, low, 2))
+            .chain([card_at(mask, kicker, None)])
+            .collect(),
+        Classification::Pair { pair, kickers } => cards_of_rank(mask, pair, 2)
+            .into_iter()
+            .chain(kickers.iter().map(|&rank| card_at(mask, rank, None)))
+            .collect(),
+        Classification::HighCard(ranks) => ranks.iter().map(|&rank| card_at(mask, rank, None)).collect(),
+    };
+    cards.try_into().expect("classification always yields 5 cards")
+}
+
+fn cards_of_rank(mask: u64, rank: u8, count: usize) -> Vec<Card> {
+    (0..4u8)
+        .filter(|suit| mask & (1 << (rank * 4 + suit)) != 0)
+        .take(count)
+        .map(|suit| Card::from_bit_index(rank * 4 + suit))
+        .collect()
+}
+
+fn card_at(mask: u64, rank: u8, suit: Option<u8>) -> Card {
+    let suit = suit.unwrap_or_else(|| {
+        (0..4u8)
+            .find(|suit| mask & (1 << (rank * 4 + suit)) != 0)
+            .expect("rank present in mask")
+    });
+    Card::from_bit_index(rank * 4 + suit)
+}
+
+// The rank indices (`Two` = 0 .. `Ace` = 12) making up the straight
+// topping out at `top`, wheel (A-2-3-4-5) included.
+fn straight_ranks(top: u8) -> [u8; 5] {
+    if top == 3 {
+        [3, 2, 1, 0, 12]
+    } else {
+        [top, top - 1, top - 2, top - 3, top - 4]
+    }
+}
+
+// Ranks still available once the classifying group(s) are removed,
+// sorted purely by rank value (never by leftover group size) so a kicker
+// slot always goes to the single highest-ranked leftover card.
+fn remaining_ranks(groups: &[(u32, u8)], exclude: &[u8]) -> Vec<u8> {
+    let mut ranks: Vec<u8> = groups
+        .iter()
+        .filter(|(_, rank)| !exclude.contains(rank))
+        .map(|&(_, rank)| rank)
+        .collect();
+    ranks.sort_unstable_by(|a, b| b.cmp(a));
+    ranks
+}
+
+fn flush_lane(lanes: &[u16; 4]) -> Option<(u8, u16)> {
+    lanes
+        .iter()
+        .enumerate()
+        .find(|(_, lane)| lane.count_ones() >= 5)
+        .map(|(suit, &lane)| (suit as u8, lane))
+}
+
+fn suit_lane(mask: u64, suit: u8) -> u16 {
+    let mut lane = 0u16;
+    for rank in 0..13u8 {
+        if mask & (1 << (rank * 4 + suit)) != 0 {
+            lane |= 1 << rank;
+        }
+    }
+    lane
+}
+
+fn straight_top(lane: u16) -> Option<u8> {
+    for top in (4..13u8).rev() {
+        let window: u16 = 0b11111 << (top - 4);
+        if lane & window == window {
+            return Some(top);
+        }
+    }
+    if lane & WHEEL == WHEEL {
+        return Some(3);
+    }
+    None
+}
+
+fn ranks_desc(lane: u16) -> Vec<u8> {
+    (0..13u8).rev().filter(|&rank| lane & (1 << rank) != 0).collect()
+}
+
+fn pack_kickers(ranks: &[u8]) -> u32 {
+    ranks.iter().fold(0u32, |acc, &rank| (acc << 4) | rank as u32)
+}
+
+fn top5(lane: u16) -> [u8; 5] {
+    let ranks = ranks_desc(lane);
+    ranks[..5].try_into().expect("at least 5 ranks")
+}
+
+fn pack(class: u32, kicker: u32) -> u32 {
+    (class << 20) | kicker
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Rank;
+
+    fn mask(cards: &str) -> u64 {
+        mask_for(&cards.split(' ').map(|c| c.parse().unwrap()).collect::<Vec<Card>>())
+    }
+
+    #[test]
+    fn quad_kicker_prefers_highest_leftover_rank_not_highest_leftover_count() {
+        // AAAA K QQ: the kicker should be the King, not the Queen, even
+        // though the leftover Queens outnumber the lone King.
+        let hand = best_five(mask("Ah Ad Ac As Kh Qh Qd"));
+        assert!(hand.iter().any(|c| c.rank == Rank::King));
+        assert!(!hand.iter().any(|c| c.rank == Rank::Queen));
+    }
+
+    #[test]
+    fn two_pair_kicker_prefers_highest_leftover_rank_not_highest_leftover_count() {
+        // KK QQ 22 A: the kicker should be the lone Ace, not a Two, even
+        // though the leftover Twos form a pair and the Ace doesn't.
+        let hand = best_five(mask("Kh Kd Qh Qd 2h 2d Ac"));
+        assert!(hand.iter().any(|c| c.rank == Rank::Ace));
+        assert!(!hand.iter().any(|c| c.rank == Rank::Two));
+    }
+
+    #[test]
+    fn wheel_straight_is_five_high_not_ten_high() {
+        // A-2-3-4-5 is the lowest straight ("the wheel"); the Ace plays
+        // low here, not as the card above King. Regression test for a
+        // bitmask bug where WHEEL was built wrong and this board
+        // misclassified as no straight at all.
+        let m = mask("Ah 2d 3c 4s 5h 9c 9d");
+        let score = evaluate7(m);
+        assert_eq!(score >> 20, STRAIGHT);
+
+        let hand = best_five(m);
+        for rank in [Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five] {
+            assert!(hand.iter().any(|c| c.rank == rank), "missing {rank:?}");
+        }
+        assert!(!hand.iter().any(|c| c.rank == Rank::Nine));
+    }
+
+    #[test]
+    fn evaluate7_matches_best_five_for_a_straight() {
+        let m = mask("9h 8d 7c 6s 5h 2c 2d");
+        let score = evaluate7(m);
+        assert_eq!(score >> 20, STRAIGHT);
+        let hand = best_five(m);
+        assert!(hand.iter().any(|c| c.rank == Rank::Nine));
+        assert!(!hand.iter().any(|c| c.rank == Rank::Two));
+    }
+}
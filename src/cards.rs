@@ -2,6 +2,8 @@ use itertools::Itertools;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use rand::{seq::SliceRandom, thread_rng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Display;
 use std::str::FromStr;
@@ -27,7 +29,7 @@ impl Deck {
     }
 }
 
-#[derive(Clone, Debug, Copy, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Clone, Debug, Copy, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
@@ -39,6 +41,29 @@ impl Card {
             .cartesian_product(Suit::iter())
             .map(|(rank, suit)| Card { rank, suit })
     }
+
+    // A designated wild rank (e.g. deuces) can stand in for any other
+    // card when evaluating `Hand::hand_type_with_wild`.
+    pub fn is_wild(&self, wild_rank: Rank) -> bool {
+        self.rank == wild_rank
+    }
+
+    // Index into a 52-bit card set, used by the fast bitmask evaluator.
+    pub fn bit_index(&self) -> u8 {
+        self.rank as u8 * 4 + self.suit as u8
+    }
+
+    // Inverse of `bit_index`.
+    pub fn from_bit_index(index: u8) -> Self {
+        let rank = Rank::from_u8(index / 4).expect("valid rank index");
+        let suit = match index % 4 {
+            0 => Suit::Hearts,
+            1 => Suit::Clubs,
+            2 => Suit::Spades,
+            _ => Suit::Diamonds,
+        };
+        Card { rank, suit }
+    }
 }
 
 impl Display for Card {
@@ -81,7 +106,9 @@ impl FromStr for Card {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, EnumIter, Eq, Ord, FromPrimitive)]
+#[derive(
+    Clone, Copy, PartialEq, PartialOrd, Debug, EnumIter, Eq, Ord, Hash, FromPrimitive, Serialize, Deserialize,
+)]
 pub enum Rank {
     Two,
     Three,
@@ -154,7 +181,7 @@ impl FromStr for Rank {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Debug, EnumIter, Eq)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Debug, EnumIter, Eq, Hash, Serialize, Deserialize)]
 pub enum Suit {
     Hearts,
     Clubs,
@@ -193,6 +220,82 @@ impl FromStr for Suit {
     }
 }
 
+// A validated, duplicate-free collection of cards, e.g. a community
+// board or a range of hole cards. Also usable as a `Board`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CardSet(Vec<Card>);
+
+pub type Board = CardSet;
+
+impl CardSet {
+    pub fn cards(&self) -> &[Card] {
+        &self.0
+    }
+}
+
+// Deriving `Deserialize` on the tuple field would bypass the
+// duplicate-card check `FromStr` enforces, so this re-runs it.
+impl<'de> Deserialize<'de> for CardSet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let cards = Vec::<Card>::deserialize(deserializer)?;
+        let mut seen = HashSet::new();
+        for card in &cards {
+            if !seen.insert(*card) {
+                return Err(serde::de::Error::custom(CardSetParseError::DuplicateCard(
+                    *card,
+                )));
+            }
+        }
+        Ok(CardSet(cards))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CardSetParseError {
+    #[error("error parsing card: {0}")]
+    CardParseError(#[from] CardParseError),
+
+    #[error("duplicate card: {0}")]
+    DuplicateCard(Card),
+}
+
+impl FromStr for CardSet {
+    type Err = CardSetParseError;
+
+    // Parses space- or concatenation-delimited card notation, e.g.
+    // "AhKd 7c 7d 2s" or "AhKd7c7d2s".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut seen = HashSet::new();
+        let mut cards = Vec::new();
+        let mut rest = s.trim();
+
+        while !rest.is_empty() {
+            if rest.starts_with(char::is_whitespace) {
+                rest = rest.trim_start();
+                continue;
+            }
+
+            let token_len = if rest.starts_with("10") { 3 } else { 2 };
+            if token_len > rest.len() {
+                return Err(CardParseError::IncompleteError.into());
+            }
+            let (token, remainder) = rest.split_at(token_len);
+
+            let card = token.parse::<Card>()?;
+            if !seen.insert(card) {
+                return Err(CardSetParseError::DuplicateCard(card));
+            }
+            cards.push(card);
+            rest = remainder;
+        }
+
+        Ok(CardSet(cards))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::cards::*;
@@ -206,4 +309,33 @@ mod tests {
         let card = "ac".parse::<Card>().expect("should not fail");
         assert_eq!(format!("{card}"), "A♣");
     }
+
+    #[test]
+    fn parse_card_set() {
+        let board = "AhKd 7c 7d 2s".parse::<CardSet>().expect("should not fail");
+        assert_eq!(board.cards().len(), 5);
+
+        let board = "AhKd7c7d2s".parse::<CardSet>().expect("should not fail");
+        assert_eq!(board.cards().len(), 5);
+
+        let err = "AhAh".parse::<CardSet>().expect_err("should reject duplicate");
+        assert!(matches!(err, CardSetParseError::DuplicateCard(_)));
+    }
+
+    #[test]
+    fn deserializing_duplicate_cards_is_rejected() {
+        let result: Result<CardSet, _> = serde_json::from_str(
+            r#"[{"rank":"Ace","suit":"Hearts"},{"rank":"Ace","suit":"Hearts"}]"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn deserializing_unique_cards_succeeds() {
+        let set: CardSet = serde_json::from_str(
+            r#"[{"rank":"Ace","suit":"Hearts"},{"rank":"King","suit":"Diamonds"}]"#,
+        )
+        .expect("should deserialize");
+        assert_eq!(set.cards().len(), 2);
+    }
 }
@@ -1,15 +1,32 @@
-use crate::cards::{Card, CardParseError, Rank};
+use crate::cards::{Card, CardParseError, Rank, Suit};
 use itertools::Itertools;
+use num_traits::FromPrimitive;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Display;
 use std::str::FromStr;
+use strum::IntoEnumIterator;
 use thiserror::Error;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Hand([Card; 5]);
 
-#[derive(Clone, PartialOrd, Eq, Ord, PartialEq, Debug)]
+// Deserializing five arbitrary cards must not bypass the
+// sorted-descending invariant `hand_type`'s grouping relies on, so this
+// routes through `Hand::from` instead of deriving the impl.
+impl<'de> Deserialize<'de> for Hand {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let cards = <[Card; 5]>::deserialize(deserializer)?;
+        Ok(Hand::from(&cards))
+    }
+}
+
+#[derive(Clone, PartialOrd, Eq, Ord, PartialEq, Debug, Serialize, Deserialize)]
 pub enum HandType {
     HighCard,
     Pair(Rank),
@@ -20,6 +37,7 @@ pub enum HandType {
     FullHouse(Rank, Rank),
     FourOfAKind(Rank),
     StraightFlush(Rank),
+    FiveOfAKind(Rank),
 }
 
 impl Hand {
@@ -34,16 +52,18 @@ impl Hand {
     }
 
     pub fn best_hand(cards: &[Card]) -> Self {
-        cards
-            .iter()
-            .cloned()
-            .combinations(5)
-            .map(|mut perm| {
-                perm.sort_by(|a, b| b.cmp(a));
-                Hand(perm.try_into().expect("size should be 5"))
-            })
-            .max()
-            .expect("at least five cards must be provided")
+        assert!(cards.len() >= 5, "at least five cards must be provided");
+        let mask = crate::bitset::mask_for(cards);
+        let mut best = crate::bitset::best_five(mask);
+        best.sort_by(|a, b| b.cmp(a));
+        Hand(best)
+    }
+
+    // Totally-ordered score from the fast bitmask evaluator, used in
+    // place of `Ord` when ranking many hands to avoid repeatedly
+    // rebuilding `hand_type`.
+    pub fn score(&self) -> u32 {
+        crate::bitset::evaluate7(crate::bitset::mask_for(&self.0))
     }
 
     pub fn hand_type(&self) -> HandType {
@@ -56,9 +76,7 @@ impl Hand {
         } else if self.is_flush() {
             HandType::Flush
         } else {
-            let ranks = self.ranks();
-            let mut groups = ranks.into_iter().dedup_with_count().collect_vec();
-            groups.sort_by(|a, b| b.cmp(a));
+            let groups = self.grouped();
             match groups[0].0 {
                 2 if groups[1].0 == 2 => HandType::TwoPair(groups[0].1, groups[1].1),
                 2 => HandType::Pair(groups[0].1),
@@ -70,6 +88,25 @@ impl Hand {
         }
     }
 
+    // Ranks grouped by how many cards share them, descending by group
+    // size and then by rank (e.g. a full house yields `[(3, trips), (2,
+    // pair)]`). Used both to classify the hand and, via
+    // `comparison_key`, to break ties between hands of the same type.
+    fn grouped(&self) -> Vec<(usize, Rank)> {
+        let mut groups = self.ranks().into_iter().dedup_with_count().collect_vec();
+        groups.sort_by(|a, b| b.cmp(a));
+        groups
+    }
+
+    // Tie-break key for hands that share a `HandType`: the grouped
+    // ranks (quad/trip/pair ranks first, highest group first) followed
+    // by the remaining kickers in descending order. Exposed so callers
+    // can compute it once per hand when sorting; it does not encode the
+    // `HandType`, so hands of different types are not ordered by it.
+    pub fn comparison_key(&self) -> Vec<Rank> {
+        self.grouped().into_iter().map(|(_, rank)| rank).collect()
+    }
+
     fn is_flush(&self) -> bool {
         let suit = self.0[0].suit;
         self.0.iter().all(|card| suit == card.suit)
@@ -95,6 +132,178 @@ impl Hand {
             None
         }
     }
+
+    // Hand strength when cards of `wild_rank` (e.g. deuces) stand in for
+    // whatever rank/suit best completes the hand. Evaluates the
+    // group-based (pair/trips/quads) and straight/flush interpretations
+    // separately and returns the stronger of the two, since a wild can't
+    // serve both at once.
+    pub fn hand_type_with_wild(&self, wild_rank: Rank) -> HandType {
+        let wild_count = self.0.iter().filter(|card| card.is_wild(wild_rank)).count();
+        if wild_count == 5 {
+            return HandType::FiveOfAKind(Rank::Ace);
+        }
+
+        let grouped = self.grouped_type_with_wild(wild_rank, wild_count);
+        match self.straight_type_with_wild(wild_rank, wild_count) {
+            Some(straight) if straight > grouped => straight,
+            _ => grouped,
+        }
+    }
+
+    // Like `Ord`, but with `wild_rank` cards treated as wild: `HandType`
+    // decides first, and ties break on the hand's actual cards with any
+    // genuine wild card counted as the lowest possible value (never its
+    // own rank), since it isn't really the card it's standing in for.
+    pub fn cmp_with_wild(&self, other: &Self, wild_rank: Rank) -> Ordering {
+        match self
+            .hand_type_with_wild(wild_rank)
+            .cmp(&other.hand_type_with_wild(wild_rank))
+        {
+            Ordering::Equal => self
+                .wild_comparison_key(wild_rank)
+                .cmp(&other.wild_comparison_key(wild_rank)),
+            other => other,
+        }
+    }
+
+    fn wild_comparison_key(&self, wild_rank: Rank) -> Vec<i8> {
+        let mut ranks: Vec<i8> = self
+            .0
+            .iter()
+            .map(|card| {
+                if card.is_wild(wild_rank) {
+                    -1
+                } else {
+                    card.rank as i8
+                }
+            })
+            .collect();
+        ranks.sort_by(|a, b| b.cmp(a));
+        ranks
+    }
+
+    fn grouped_type_with_wild(&self, wild_rank: Rank, wild_count: usize) -> HandType {
+        let mut counts = [0usize; 13];
+        for card in self.0.iter().filter(|card| !card.is_wild(wild_rank)) {
+            counts[card.rank as usize] += 1;
+        }
+
+        let mut groups: Vec<(usize, Rank)> = counts
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count > 0)
+            .map(|(rank, &count)| (count, Rank::from_usize(rank).expect("valid rank index")))
+            .collect();
+        groups.sort_by(|a, b| b.cmp(a));
+
+        // Wilds always boost the biggest existing group; splitting them
+        // across groups can never beat concentrating them on the leader.
+        groups[0].0 += wild_count;
+
+        match groups[0].0 {
+            n if n >= 5 => HandType::FiveOfAKind(groups[0].1),
+            4 => HandType::FourOfAKind(groups[0].1),
+            3 if matches!(groups.get(1), Some(&(2, _))) => {
+                HandType::FullHouse(groups[0].1, groups[1].1)
+            }
+            3 => HandType::ThreeOfAKind(groups[0].1),
+            2 if matches!(groups.get(1), Some(&(2, _))) => {
+                HandType::TwoPair(groups[0].1, groups[1].1)
+            }
+            2 => HandType::Pair(groups[0].1),
+            _ => HandType::HighCard,
+        }
+    }
+
+    fn straight_type_with_wild(&self, wild_rank: Rank, wild_count: usize) -> Option<HandType> {
+        if wild_count == 0 {
+            return match (self.extract_straight(), self.is_flush()) {
+                (Some(top), true) => Some(HandType::StraightFlush(top)),
+                (Some(top), false) => Some(HandType::Straight(top)),
+                (None, true) => Some(HandType::Flush),
+                (None, false) => None,
+            };
+        }
+
+        let non_wild: Vec<&Card> = self
+            .0
+            .iter()
+            .filter(|card| !card.is_wild(wild_rank))
+            .collect();
+        let candidates = Self::straight_candidates();
+
+        let mut suit_counts = [0usize; 4];
+        for card in &non_wild {
+            suit_counts[card.suit as usize] += 1;
+        }
+        let flush_suit = Suit::iter()
+            .enumerate()
+            .find(|&(i, _)| suit_counts[i] + wild_count >= 5)
+            .map(|(_, suit)| suit);
+
+        if let Some(suit) = flush_suit {
+            let suited: HashSet<Rank> = non_wild
+                .iter()
+                .filter(|card| card.suit == suit)
+                .map(|card| card.rank)
+                .collect();
+            // Candidates are checked highest-top-first, so the first gap
+            // a wild can plug is also the best straight flush available.
+            if let Some(&(top, _)) = candidates
+                .iter()
+                .find(|(_, ranks)| ranks.iter().filter(|r| !suited.contains(r)).count() <= wild_count)
+            {
+                return Some(HandType::StraightFlush(top));
+            }
+        }
+
+        let present: HashSet<Rank> = non_wild.iter().map(|card| card.rank).collect();
+        let straight = candidates
+            .iter()
+            .find(|(_, ranks)| ranks.iter().filter(|r| !present.contains(r)).count() <= wild_count)
+            .map(|&(top, _)| HandType::Straight(top));
+
+        match (straight, flush_suit.is_some()) {
+            (Some(straight), true) => Some(std::cmp::max(straight, HandType::Flush)),
+            (Some(straight), false) => Some(straight),
+            (None, true) => Some(HandType::Flush),
+            (None, false) => None,
+        }
+    }
+
+    // Descending list of (top rank, required ranks) for every straight,
+    // wheel (A-2-3-4-5) last since it is the weakest one.
+    fn straight_candidates() -> Vec<(Rank, Vec<Rank>)> {
+        let tops = [
+            Rank::Ace,
+            Rank::King,
+            Rank::Queen,
+            Rank::Jack,
+            Rank::Ten,
+            Rank::Nine,
+            Rank::Eight,
+            Rank::Seven,
+            Rank::Six,
+        ];
+        let mut candidates: Vec<(Rank, Vec<Rank>)> = tops
+            .into_iter()
+            .map(|top| {
+                let mut ranks = vec![top];
+                let mut current = top;
+                for _ in 0..4 {
+                    current = Rank::from_u8(current as u8 - 1).expect("in range");
+                    ranks.push(current);
+                }
+                (top, ranks)
+            })
+            .collect();
+        candidates.push((
+            Rank::Five,
+            vec![Rank::Five, Rank::Four, Rank::Three, Rank::Two, Rank::Ace],
+        ));
+        candidates
+    }
 }
 
 impl Eq for Hand {}
@@ -108,7 +317,7 @@ impl PartialEq for Hand {
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> Ordering {
         match self.hand_type().cmp(&other.hand_type()) {
-            Ordering::Equal => self.ranks().cmp(&other.ranks()),
+            Ordering::Equal => self.comparison_key().cmp(&other.comparison_key()),
             other => other,
         }
     }
@@ -135,6 +344,19 @@ impl Display for Hand {
     }
 }
 
+// Camel-Cards-style scoring: sorts `entries` ascending by hand strength
+// (the weakest hand gets rank 1) and sums `rank * bid` across all of
+// them.
+pub fn rank_and_score(entries: &[(Hand, u64)]) -> u64 {
+    let mut entries = entries.to_vec();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, (_, bid))| (index as u64 + 1) * bid)
+        .sum()
+}
+
 #[derive(Error, Debug)]
 pub enum HandParseError {
     #[error("error parsing card: {0}")]
@@ -357,4 +579,173 @@ mod tests {
         let hand = "2d,4d,3d,5d,6c".parse::<Hand>().expect("bad parse");
         assert_eq!(hand.hand_type(), HandType::Straight(Rank::Six));
     }
+
+    #[test]
+    fn deserializing_unsorted_cards_still_sorts() {
+        // Valid trip aces, but not pre-sorted descending: a naive derived
+        // `Deserialize` would leave this as-is and `hand_type` (which only
+        // dedups *adjacent* equal ranks) would misread it as a pair.
+        let hand: Hand = serde_json::from_str(
+            r#"[{"rank":"Ace","suit":"Hearts"},{"rank":"King","suit":"Hearts"},{"rank":"Ace","suit":"Clubs"},{"rank":"Ace","suit":"Spades"},{"rank":"Queen","suit":"Hearts"}]"#,
+        )
+        .expect("should deserialize");
+        assert_eq!(hand.hand_type(), HandType::ThreeOfAKind(Rank::Ace));
+    }
+
+    #[test]
+    fn score_orders_consistently_with_ord() {
+        let pair = "2d,4d,4c,5d,6d".parse::<Hand>().expect("bad parse");
+        let two_pair = "2d,4d,4c,5d,5s".parse::<Hand>().expect("bad parse");
+        assert!(two_pair > pair);
+        assert!(two_pair.score() > pair.score());
+    }
+
+    #[test]
+    fn two_pair_with_a_weaker_second_pair_still_loses_despite_a_higher_kicker() {
+        // Kh,Kd,Qc,3d,3s is TwoPair(King, Three); Kh,Kd,4c,4d,2s is
+        // TwoPair(King, Four). These are *different* `HandType`s (the
+        // second pair's rank is part of the type itself), so
+        // `hand_type().cmp()` already decides this one on its own before
+        // `comparison_key` ever runs -- it's `HandType`'s derived `Ord`,
+        // not `comparison_key`, that correctly ranks the Four-pair hand
+        // above the Three-pair hand despite its weaker (Two vs. Queen)
+        // kicker. Kept as a regression test for that user-visible
+        // behavior either way.
+        let king_three_queen = "Kh,Kd,Qc,3d,3s".parse::<Hand>().expect("bad parse");
+        let king_four_two = "Kh,Kd,4c,4d,2s".parse::<Hand>().expect("bad parse");
+
+        assert_ne!(king_three_queen.hand_type(), king_four_two.hand_type());
+        assert!(king_four_two > king_three_queen);
+    }
+
+    #[test]
+    fn comparison_key_breaks_ties_on_the_paired_rank_before_kickers() {
+        // Both hands are Pair(Jack) -- a `HandType` that does *not*
+        // embed its kickers -- so this is the case `comparison_key`
+        // actually exists for: it must compare the paired rank first
+        // (tied here) and only then fall through to kickers, rather
+        // than just sorting all five ranks and comparing position by
+        // position (which would let Ace, the single highest card
+        // overall, decide things by coincidence in this particular
+        // case, but is not what "best kicker wins" means in general).
+        let jacks_ace_kicker = "Jh,Jd,Ac,9d,8s".parse::<Hand>().expect("bad parse");
+        let jacks_king_kicker = "Jh,Jd,Kc,7d,6s".parse::<Hand>().expect("bad parse");
+
+        assert_eq!(jacks_ace_kicker.hand_type(), jacks_king_kicker.hand_type());
+        assert!(jacks_ace_kicker > jacks_king_kicker);
+    }
+
+    #[test]
+    fn best_hand_picks_the_strongest_five_of_seven() {
+        // Seven cards containing a made flush and two pair; best_hand must
+        // evaluate the whole mask directly (no more best-of-C(7,5) search)
+        // and still land on the flush.
+        let cards: Vec<Card> = "2h,9h,Qh,Kh,Ah,2d,9d"
+            .split(',')
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let hand = Hand::best_hand(&cards);
+        assert_eq!(hand.hand_type(), HandType::Flush);
+    }
+
+    #[test]
+    fn rank_and_score_weights_weakest_hand_lowest() {
+        let pair = "2d,4d,4c,5d,6d".parse::<Hand>().expect("bad parse");
+        let two_pair = "2d,4d,4c,5d,5s".parse::<Hand>().expect("bad parse");
+        let trips = Hand::from(&[
+            Card {
+                rank: Rank::Four,
+                suit: Suit::Hearts,
+            },
+            Card {
+                rank: Rank::Four,
+                suit: Suit::Diamonds,
+            },
+            Card {
+                rank: Rank::Four,
+                suit: Suit::Clubs,
+            },
+            Card {
+                rank: Rank::Five,
+                suit: Suit::Diamonds,
+            },
+            Card {
+                rank: Rank::Ace,
+                suit: Suit::Spades,
+            },
+        ]);
+
+        // Ranks 1, 2, 3 go to pair, two_pair, trips respectively (weakest
+        // first), bids 10, 20, 30: 1*10 + 2*20 + 3*30 = 140.
+        let score = rank_and_score(&[(two_pair, 20), (trips, 30), (pair, 10)]);
+        assert_eq!(score, 140);
+    }
+
+    #[test]
+    fn wild_card_can_upgrade_pair_to_straight() {
+        // Present ranks 8,9,10,J form a pair-of-Jacks if the wild just
+        // boosts the existing group, but the wild can instead plug the gap
+        // at Queen for a Straight(Queen) -- strictly stronger, so that's
+        // what should win.
+        let hand = "8h,9d,10c,Js,2h".parse::<Hand>().expect("bad parse");
+        assert_eq!(
+            hand.hand_type_with_wild(Rank::Two),
+            HandType::Straight(Rank::Queen)
+        );
+    }
+
+    #[test]
+    fn wild_card_all_five_is_five_of_a_kind_aces() {
+        let hand = Hand::from(&[
+            Card {
+                rank: Rank::Two,
+                suit: Suit::Hearts,
+            },
+            Card {
+                rank: Rank::Two,
+                suit: Suit::Diamonds,
+            },
+            Card {
+                rank: Rank::Two,
+                suit: Suit::Clubs,
+            },
+            Card {
+                rank: Rank::Two,
+                suit: Suit::Spades,
+            },
+            Card {
+                rank: Rank::Two,
+                suit: Suit::Hearts,
+            },
+        ]);
+        assert_eq!(
+            hand.hand_type_with_wild(Rank::Two),
+            HandType::FiveOfAKind(Rank::Ace)
+        );
+    }
+
+    #[test]
+    fn wild_card_ties_break_as_lowest_card() {
+        // Kings are wild. `wild_filled` is missing its Seven, sandwiched
+        // between two runs (9-8 and 6-5) that aren't adjacent on their
+        // own, so the wild King has only one useful value here (a Seven)
+        // and both hands land on the same Straight(Nine) -- but the wild
+        // King must still sort as the lowest card when breaking the tie,
+        // not as a King, so the genuine Seven in `natural` wins.
+        let natural = "9h,8d,7c,6s,5h".parse::<Hand>().expect("bad parse");
+        let wild_filled = "9h,8d,6s,5h,Kh".parse::<Hand>().expect("bad parse");
+
+        assert_eq!(
+            natural.hand_type_with_wild(Rank::King),
+            HandType::Straight(Rank::Nine)
+        );
+        assert_eq!(
+            wild_filled.hand_type_with_wild(Rank::King),
+            HandType::Straight(Rank::Nine)
+        );
+        assert_eq!(
+            natural.cmp_with_wild(&wild_filled, Rank::King),
+            Ordering::Greater
+        );
+    }
 }
@@ -1,7 +1,10 @@
-use crate::cards::Card;
-use crate::hands::Hand;
+use crate::cards::{Board, Card};
+use crate::hands::{Hand, HandType};
 use itertools::Itertools;
 
+pub mod equity;
+pub mod showdown;
+
 // Given community cards, find best two cards not in hand
 // to win.
 pub fn find_nuts(community: &[Card]) -> (Hand, [Card; 2]) {
@@ -23,3 +26,104 @@ pub fn find_nuts(community: &[Card]) -> (Hand, [Card; 2]) {
         .max()
         .expect("there are many combinations of at least 2 cards")
 }
+
+// Unseen cards which, if dealt next, make the hero's best hand beat or
+// tie every villain's best hand.
+pub fn count_outs(hole: &[Card; 2], board: &Board, villains: &[[Card; 2]]) -> Vec<Card> {
+    let board = board.cards();
+    let known: Vec<Card> = hole
+        .iter()
+        .chain(board.iter())
+        .chain(villains.iter().flatten())
+        .copied()
+        .collect();
+
+    Card::iter()
+        .filter(|card| !known.contains(card))
+        .filter(|card| {
+            let mut extended_board = board.to_vec();
+            extended_board.push(*card);
+
+            let mut hero_cards = extended_board.clone();
+            hero_cards.extend_from_slice(hole);
+            let hero_hand = Hand::best_hand(&hero_cards);
+
+            villains.iter().all(|villain| {
+                let mut villain_cards = extended_board.clone();
+                villain_cards.extend_from_slice(villain);
+                hero_hand >= Hand::best_hand(&villain_cards)
+            })
+        })
+        .collect()
+}
+
+// Groups `count_outs`' results by the `HandType` the hero's hand would
+// achieve with each out, so a UI can say e.g. "9 outs to a flush, 4 to
+// a straight".
+pub fn outs_by_hand_type(
+    hole: &[Card; 2],
+    board: &Board,
+    villains: &[[Card; 2]],
+) -> Vec<(HandType, Vec<Card>)> {
+    let mut grouped: Vec<(HandType, Vec<Card>)> = Vec::new();
+
+    for card in count_outs(hole, board, villains) {
+        let mut cards = board.cards().to_vec();
+        cards.push(card);
+        cards.extend_from_slice(hole);
+        let hand_type = Hand::best_hand(&cards).hand_type();
+
+        match grouped.iter_mut().find(|(t, _)| *t == hand_type) {
+            Some((_, outs)) => outs.push(card),
+            None => grouped.push((hand_type, vec![card])),
+        }
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Rank;
+
+    fn card(s: &str) -> Card {
+        s.parse().expect("bad card")
+    }
+
+    // Hero holds an open-ended straight draw (7,8 with a 9-10 board) against
+    // a villain overpair; only a Jack or a Six completes the straight and
+    // beats the pocket aces, so there should be exactly 8 outs.
+    fn straight_draw() -> ([Card; 2], Board, Vec<[Card; 2]>) {
+        let hero = [card("7h"), card("8d")];
+        let board: Board = "9c 10s 2d".parse().expect("bad board");
+        let villains = vec![[card("Ah"), card("Ad")]];
+        (hero, board, villains)
+    }
+
+    #[test]
+    fn open_ended_straight_draw_has_eight_outs() {
+        let (hero, board, villains) = straight_draw();
+        let outs = count_outs(&hero, &board, &villains);
+        assert_eq!(outs.len(), 8);
+        assert!(outs.iter().all(|card| matches!(card.rank, Rank::Jack | Rank::Six)));
+    }
+
+    #[test]
+    fn outs_by_hand_type_groups_by_straight_top() {
+        let (hero, board, villains) = straight_draw();
+        let grouped = outs_by_hand_type(&hero, &board, &villains);
+
+        let jack_high = grouped
+            .iter()
+            .find(|(t, _)| *t == HandType::Straight(Rank::Jack))
+            .expect("Jack completes a Jack-high straight");
+        assert_eq!(jack_high.1.len(), 4);
+
+        let ten_high = grouped
+            .iter()
+            .find(|(t, _)| *t == HandType::Straight(Rank::Ten))
+            .expect("Six completes a Ten-high straight");
+        assert_eq!(ten_high.1.len(), 4);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::cards::{Board, Card};
+use crate::hands::Hand;
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+
+// Above this many ways to complete the board, exhaustive enumeration gets
+// too slow and we switch to Monte-Carlo sampling instead.
+const EXHAUSTIVE_LIMIT: usize = 1_000_000;
+const MONTE_CARLO_TRIALS: usize = 100_000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Equity {
+    pub win: f64,
+    pub tie: f64,
+    pub equity: f64,
+}
+
+// Win rate, tie rate, and fractional equity (each a fraction from 0 to 1)
+// for each player's two hole cards
+// against a (possibly incomplete) board, aligned to `hole_cards`' order.
+pub fn equity(hole_cards: &[[Card; 2]], board: &Board) -> Vec<Equity> {
+    let board = board.cards();
+    let dealt: Vec<Card> = hole_cards
+        .iter()
+        .flatten()
+        .copied()
+        .chain(board.iter().copied())
+        .collect();
+    let remaining: Vec<Card> = Card::iter().filter(|card| !dealt.contains(card)).collect();
+    let needed = 5 - board.len();
+
+    let mut wins = vec![0usize; hole_cards.len()];
+    let mut ties = vec![0usize; hole_cards.len()];
+    let mut shares = vec![0.0f64; hole_cards.len()];
+    let mut trials = 0usize;
+
+    if n_choose_k(remaining.len(), needed) <= EXHAUSTIVE_LIMIT {
+        for completion in remaining.iter().copied().combinations(needed) {
+            score_board(hole_cards, board, &completion, &mut wins, &mut ties, &mut shares);
+            trials += 1;
+        }
+    } else {
+        let mut rng = thread_rng();
+        for _ in 0..MONTE_CARLO_TRIALS {
+            let completion: Vec<Card> = remaining
+                .choose_multiple(&mut rng, needed)
+                .copied()
+                .collect();
+            score_board(hole_cards, board, &completion, &mut wins, &mut ties, &mut shares);
+            trials += 1;
+        }
+    }
+
+    (0..hole_cards.len())
+        .map(|i| Equity {
+            win: wins[i] as f64 / trials as f64,
+            tie: ties[i] as f64 / trials as f64,
+            equity: shares[i] / trials as f64,
+        })
+        .collect()
+}
+
+fn score_board(
+    hole_cards: &[[Card; 2]],
+    board: &[Card],
+    completion: &[Card],
+    wins: &mut [usize],
+    ties: &mut [usize],
+    shares: &mut [f64],
+) {
+    let full_board: Vec<Card> = board.iter().copied().chain(completion.iter().copied()).collect();
+    let hands: Vec<Hand> = hole_cards
+        .iter()
+        .map(|hole| {
+            let mut cards = full_board.clone();
+            cards.extend_from_slice(hole);
+            Hand::best_hand(&cards)
+        })
+        .collect();
+
+    let best = hands.iter().max().expect("at least one player's hand");
+    let winners: Vec<usize> = hands
+        .iter()
+        .enumerate()
+        .filter(|(_, hand)| *hand == best)
+        .map(|(i, _)| i)
+        .collect();
+
+    let share = 1.0 / winners.len() as f64;
+    for &i in &winners {
+        shares[i] += share;
+        if winners.len() == 1 {
+            wins[i] += 1;
+        } else {
+            ties[i] += 1;
+        }
+    }
+}
+
+fn n_choose_k(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1usize, |acc, i| acc * (n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(s: &str) -> Card {
+        s.parse().expect("bad card")
+    }
+
+    fn board(s: &str) -> Board {
+        s.parse().expect("bad board")
+    }
+
+    #[test]
+    fn identical_hole_cards_split_evenly() {
+        // Same two hole cards on each side can only ever tie.
+        let hole = [card("Ah"), card("Kh")];
+        let results = equity(&[hole, hole], &board("2c 7d 9s"));
+
+        assert_eq!(results.len(), 2);
+        for result in results {
+            assert_eq!(result.win, 0.0);
+            assert_eq!(result.tie, 1.0);
+            assert!((result.equity - 0.5).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn preflop_aa_is_a_big_favorite_over_kk() {
+        let aces = [card("Ah"), card("Ad")];
+        let kings = [card("Kh"), card("Kd")];
+        let results = equity(&[aces, kings], &Board::default());
+
+        // Well-known heads-up preflop matchup, roughly 80/20; Monte Carlo
+        // sampling needs some slack either way.
+        assert!(results[0].win > 0.75, "AA win rate was {}", results[0].win);
+        assert!(results[1].win < 0.25, "KK win rate was {}", results[1].win);
+        // Each trial splits exactly one unit of equity between the two
+        // players (ties counted double in raw win/tie, so check `equity`).
+        assert!((results[0].equity + results[1].equity - 1.0).abs() < 1e-9);
+    }
+}
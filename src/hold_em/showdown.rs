@@ -0,0 +1,135 @@
+use crate::cards::{Board, Card};
+use crate::hands::Hand;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Result of resolving a showdown: which players tied for which rank
+// (strongest hand first) and how the pot(s) were split between them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Showdown<P: Eq + Hash> {
+    pub ranking: Vec<Vec<P>>,
+    pub payouts: HashMap<P, u64>,
+}
+
+impl<P: Copy + Eq + Hash> Showdown<P> {
+    // `entries` is every player still in the hand at showdown, along
+    // with the total chips each contributed to the pot. Handles split
+    // pots (tied hands) and all-in side pots (unequal contributions).
+    pub fn resolve(board: &Board, entries: &[(P, [Card; 2], u64)]) -> Self {
+        let board = board.cards();
+        let hands: Vec<(P, Hand, u64)> = entries
+            .iter()
+            .map(|&(player, hole, contributed)| {
+                let mut cards = board.to_vec();
+                cards.extend_from_slice(&hole);
+                (player, Hand::best_hand(&cards), contributed)
+            })
+            .collect();
+
+        Showdown {
+            ranking: Self::rank(&hands),
+            payouts: Self::distribute(&hands),
+        }
+    }
+
+    // Players grouped into equivalence classes by `Hand` equality,
+    // strongest first.
+    fn rank(hands: &[(P, Hand, u64)]) -> Vec<Vec<P>> {
+        let mut groups: Vec<(&Hand, Vec<P>)> = Vec::new();
+        for (player, hand, _) in hands {
+            match groups.iter_mut().find(|(h, _)| *h == hand) {
+                Some((_, players)) => players.push(*player),
+                None => groups.push((hand, vec![*player])),
+            }
+        }
+        groups.sort_by(|a, b| b.0.cmp(a.0));
+        groups.into_iter().map(|(_, players)| players).collect()
+    }
+
+    // Walks contribution tiers from smallest to largest, building a side
+    // pot per tier and awarding it to the best-ranked players still
+    // eligible (i.e. who contributed at least that tier), splitting any
+    // remainder chip-by-chip among them.
+    fn distribute(hands: &[(P, Hand, u64)]) -> HashMap<P, u64> {
+        let mut payouts: HashMap<P, u64> =
+            hands.iter().map(|(player, _, _)| (*player, 0)).collect();
+
+        let mut tiers: Vec<u64> = hands.iter().map(|(_, _, contributed)| *contributed).collect();
+        tiers.sort_unstable();
+        tiers.dedup();
+
+        let mut prev_tier = 0u64;
+        for tier in tiers {
+            let contributors: Vec<&(P, Hand, u64)> = hands
+                .iter()
+                .filter(|(_, _, contributed)| *contributed >= tier)
+                .collect();
+            let pot = (tier - prev_tier) * contributors.len() as u64;
+            prev_tier = tier;
+            if pot == 0 {
+                continue;
+            }
+
+            let best = contributors
+                .iter()
+                .map(|(_, hand, _)| hand)
+                .max()
+                .expect("at least one contributor at this tier");
+            let winners: Vec<P> = contributors
+                .iter()
+                .filter(|(_, hand, _)| hand == best)
+                .map(|(player, _, _)| *player)
+                .collect();
+
+            let share = pot / winners.len() as u64;
+            let mut remainder = pot % winners.len() as u64;
+            for player in winners {
+                let mut amount = share;
+                if remainder > 0 {
+                    amount += 1;
+                    remainder -= 1;
+                }
+                *payouts.get_mut(&player).expect("player present in payouts") += amount;
+            }
+        }
+
+        payouts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(s: &str) -> Card {
+        s.parse().expect("bad card")
+    }
+
+    #[test]
+    fn three_way_side_pot_with_a_tied_hand() {
+        // Board is unpaired, no straight or flush: player 3's pair of
+        // fours beats players 1 and 2's identical ace-king high card, and
+        // 1/2 are an exact tie against each other.
+        let board: Board = "2c 7d 9h 10s 3d".parse().expect("bad board");
+        let entries = [
+            (1, [card("Ah"), card("Kh")], 100),
+            (2, [card("Ad"), card("Kd")], 100),
+            (3, [card("4h"), card("4d")], 50),
+        ];
+
+        let showdown = Showdown::resolve(&board, &entries);
+
+        assert_eq!(showdown.ranking.len(), 2);
+        assert_eq!(showdown.ranking[0], vec![3]);
+        let mut second_tier = showdown.ranking[1].clone();
+        second_tier.sort();
+        assert_eq!(second_tier, vec![1, 2]);
+
+        // Player 3's short stack only contests the first 50 from each
+        // player (150 total) and wins it outright; the remaining 50-50
+        // side pot between 1 and 2 is split evenly since they tied.
+        assert_eq!(showdown.payouts[&3], 150);
+        assert_eq!(showdown.payouts[&1], 50);
+        assert_eq!(showdown.payouts[&2], 50);
+    }
+}
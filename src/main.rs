@@ -1,13 +1,74 @@
+mod bitset;
 mod cards;
 mod hands;
 mod hold_em;
 
+use cards::{Board, Card, Suit};
+use hands::rank_and_score;
+use hold_em::{equity::equity, showdown::Showdown};
+
 fn main() {
     let mut deck = cards::Deck::new();
     let flop = deck.deal_n::<3>().expect("deck too small");
     println!("Flop: {} {} {}", flop[0], flop[1], flop[2]);
-    let (hand, cards) = hold_em::find_nuts(&flop).expect("can always find nuts with flop of 3");
-    println!("Nut cards: {} {}", cards[0], cards[1]);
+    let (hand, nut_cards) = hold_em::find_nuts(&flop);
+    println!("Nut cards: {} {}", nut_cards[0], nut_cards[1]);
     println!("Nut hand: {hand}");
     println!("This is a {:?}", hand.hand_type());
+
+    let hero = deck.deal_n::<2>().expect("deck too small");
+    let villain = deck.deal_n::<2>().expect("deck too small");
+    let preflop = equity(&[hero, villain], &Board::default());
+    println!(
+        "Preflop equity: hero {:.1}%, villain {:.1}%",
+        preflop[0].equity * 100.0,
+        preflop[1].equity * 100.0
+    );
+
+    let flop_board = board_of(&flop);
+    for (hand_type, outs) in hold_em::outs_by_hand_type(&hero, &flop_board, &[villain]) {
+        println!("{} outs to {hand_type:?}", outs.len());
+    }
+
+    let turn = deck.deal_n::<1>().expect("deck too small");
+    let river = deck.deal_n::<1>().expect("deck too small");
+    let full_board = board_of(&[flop[0], flop[1], flop[2], turn[0], river[0]]);
+    let showdown = Showdown::resolve(&full_board, &[("hero", hero, 100), ("villain", villain, 100)]);
+    println!("Showdown ranking: {:?}", showdown.ranking);
+    println!("Showdown payouts: {:?}", showdown.payouts);
+
+    println!("Bitmask evaluator score for the nut hand: {}", hand.score());
+
+    let deuces_wild = hand.hand_type_with_wild(cards::Rank::Two);
+    println!("With deuces wild, the nut hand is a {deuces_wild:?}");
+    println!(
+        "Deuces-wild tie-break vs. itself: {:?}",
+        hand.cmp_with_wild(&hand, cards::Rank::Two)
+    );
+    println!(
+        "Camel-cards score for a lone bid of 10 on the nut hand: {}",
+        rank_and_score(&[(hand, 10)])
+    );
+}
+
+// `Board` is built only via `FromStr`/`Default`/`Deserialize`, so a set of
+// already-dealt `Card`s is round-tripped through the letter-suit notation
+// it parses (e.g. "AhKd 7c") -- `Card`'s own `Display` uses suit symbols,
+// which `CardSet::from_str`'s fixed-width tokenizer doesn't expect.
+fn board_of(cards: &[Card]) -> Board {
+    cards
+        .iter()
+        .map(|card| {
+            let suit = match card.suit {
+                Suit::Hearts => 'h',
+                Suit::Clubs => 'c',
+                Suit::Spades => 's',
+                Suit::Diamonds => 'd',
+            };
+            format!("{}{suit}", card.rank)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+        .parse()
+        .expect("dealt cards always form a valid board")
 }